@@ -85,9 +85,12 @@ impl GetIPCInfo {
             GLOBAL_IPC_INFO.pages_mapped = paging_info.pages_mapped;
             GLOBAL_IPC_INFO.valid = true;
             
-            // Copy targeting data
-            let target_slice = NEXUS6_CODENAME.as_bytes();
-            GLOBAL_IPC_INFO.target_device[..target_slice.len()].copy_from_slice(target_slice);
+            // Copy targeting data (codename resolved from the property table)
+            let codename = crate::devprops::get_property("target.codename")
+                .unwrap_or(NEXUS6_CODENAME);
+            let target_slice = codename.as_bytes();
+            let n = target_slice.len().min(GLOBAL_IPC_INFO.target_device.len());
+            GLOBAL_IPC_INFO.target_device[..n].copy_from_slice(&target_slice[..n]);
             
             let lock_slice = b"Global";
             GLOBAL_IPC_INFO.lock_type[..lock_slice.len()].copy_from_slice(lock_slice);
@@ -96,34 +99,64 @@ impl GetIPCInfo {
         }
     }
     
-    /// GetIPCTargetingInfo - Complete targeting subsystem info
+    /// GetIPCTargetingInfo - Complete targeting subsystem info. The codename and
+    /// allowed-UID set come from the device-property table parsed at boot,
+    /// falling back to the built-in Nexus 6 defaults when unset.
     pub fn get_ipc_targeting_info(&self) -> IPCTargetingInfo {
         let mut info = IPCTargetingInfo {
-            target_device: *b"Motorola Nexus 6 shamu                                                                                                    ",
-            allowed_uids: [SYSTEM_UID, 1001, 0, 0, 0, 0, 0, 0],
-            uid_count: 2,
+            target_device: [0u8; 64],
+            allowed_uids: [0u32; 8],
+            uid_count: 0,
             target_valid: true,
             enforced: true,
             lock_active: false,
         };
-        
+
+        let codename = crate::devprops::get_property("target.codename")
+            .unwrap_or(NEXUS6_CODENAME);
+        let codename = codename.as_bytes();
+        let n = codename.len().min(info.target_device.len());
+        info.target_device[..n].copy_from_slice(&codename[..n]);
+
+        // Allowed UIDs are sized from the property table; the FFI struct keeps a
+        // fixed window, so only the first entries are mirrored into it while the
+        // full set lives in crate::devprops::allowed_uids().
+        let allowed = crate::devprops::allowed_uids();
+        if allowed.is_empty() {
+            info.allowed_uids[0] = SYSTEM_UID;
+            info.allowed_uids[1] = 1001;
+            info.uid_count = 2;
+        } else {
+            let m = allowed.len().min(info.allowed_uids.len());
+            info.allowed_uids[..m].copy_from_slice(&allowed[..m]);
+            info.uid_count = allowed.len() as u32;
+        }
+
         // OSServer targeting validation
         unsafe {
             if let Some(osserver) = crate::osserver::OS_SERVER {
-                info.target_valid = (*osserver).validate_target(NEXUS6_CODENAME);
+                let codename = crate::devprops::get_property("target.codename")
+                    .unwrap_or(NEXUS6_CODENAME);
+                info.target_valid = (*osserver).validate_target(codename);
             }
         }
-        
+
         info
     }
     
     /// GetIPCPagingInfo - Complete paging subsystem info  
     pub fn get_ipc_paging_info(&self) -> IPCPagingInfo {
+        // Page size and region extent come from the device-property table when
+        // present, so porting to another ARMv7a target is a blob edit.
+        let page_size = crate::devprops::get_property_usize("ipc.page_size")
+            .unwrap_or(MEM_PAGE_SIZE);
+        let region_pages = crate::devprops::get_property_u32("ipc.region_pages")
+            .unwrap_or(64);
         IPCPagingInfo {
-            page_size: MEM_PAGE_SIZE,
-            pages_mapped: 64,  // 1MB IPC region / 16kb
+            page_size,
+            pages_mapped: region_pages,
             l1_table_base: KERNEL_BASE + 0x4000,
-            total_ipc_region: MEM_PAGE_SIZE * 64,
+            total_ipc_region: page_size * region_pages as usize,
             cache_flags: 0x5,  // WBWA
             tlb_flushes: 0,
             valid: true,
@@ -139,8 +172,15 @@ impl GetIPCInfo {
         }
     }
     
-    fn validate_uid(&self, uid: u32) -> bool {
-        uid == SYSTEM_UID || uid == 1001  // system + radio
+    pub fn validate_uid(&self, uid: u32) -> bool {
+        // Consult the allowed-UID property table; fall back to the built-in
+        // system + radio pair when no blob has been parsed.
+        let allowed = crate::devprops::allowed_uids();
+        if allowed.is_empty() {
+            uid == SYSTEM_UID || uid == 1001  // system + radio
+        } else {
+            allowed.contains(&uid)
+        }
     }
     
     fn invalid_ipc_info(&self) -> IPCInfo {
@@ -201,6 +241,30 @@ pub extern "C" fn get_ipc_paging_info() -> IPCPagingInfo {
     GetIPCInfo::new().get_ipc_paging_info()
 }
 
+/// Query a single named device property. Lets binder clients read individual
+/// values (e.g. `target.codename`, `ipc.page_size`) from the boot-time blob.
+/// Writes up to `out_len` bytes of the value into `out` and returns the number
+/// of bytes written, or `-EINVAL` (-22) when the property is unset.
+#[no_mangle]
+pub extern "C" fn get_ipc_property(name: *const u8, name_len: usize, out: *mut u8, out_len: usize) -> i32 {
+    unsafe {
+        let name_bytes = slice::from_raw_parts(name, name_len);
+        let name = match core::str::from_utf8(name_bytes) {
+            Ok(s) => s,
+            Err(_) => return -22,
+        };
+        match crate::devprops::get_property(name) {
+            Some(value) => {
+                let bytes = value.as_bytes();
+                let n = bytes.len().min(out_len);
+                ptr::copy_nonoverlapping(bytes.as_ptr(), out, n);
+                n as i32
+            }
+            None => -22,
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn dump_ipc_info() {
     let info = GetIPCInfo::new().get_ipc_info(SYSTEM_UID);
@@ -226,18 +290,71 @@ pub extern "C" fn dump_ipc_info() {
 
 // ======================== BINDER SERVICE INTEGRATION ========================
 
+/// Publish the completed transaction to the GIC and raise the binder
+/// transaction-completion IRQ, which dispatches to the one handler registered
+/// on that line (the monitoring hook). Setting the pending
+/// transaction first means handlers never observe a null pointer.
+fn raise_binder_completion_irq(txn: *mut crate::main::BinderTransaction) {
+    crate::gic::set_pending_transaction(txn);
+    // Dispatch the known completion line directly: this is a software-driven
+    // completion, so there is no pending hardware IRQ for GICC_IAR to report.
+    crate::gic::gic_dispatch_line(crate::gic::IRQ_BINDER_TXN_COMPLETE);
+}
+
 #[no_mangle]
 pub extern "C" fn binder_get_ipc_info_handler(txn: *mut crate::main::BinderTransaction) -> i32 {
     unsafe {
         let txn = &mut *txn;
-        if txn.sender_uid != SYSTEM_UID && txn.sender_uid != 1001 {
+        // Access gate via validate_uid so the device-property allowed-UID table
+        // is authoritative (a blob may permit UIDs beyond system + radio).
+        if !GetIPCInfo::new().validate_uid(txn.sender_uid) {
             return -13;  // EACCES
         }
-        
+
+        // Reject transactions from a UID that has exhausted its bandwidth quota
+        // for the current period; the sampler refills it at the next edge.
+        if GetIPCInfo::new().bw_is_throttled(txn.sender_uid) {
+            return -11;  // EAGAIN
+        }
+
+        // Speculatively update shared state under a checkpoint so a failed
+        // transaction rolls back cleanly instead of leaving it half-applied.
+        let getter = GetIPCInfo::new();
+        let start = getter.rdtsc();
+        let checkpoint = getter.ipc_checkpoint();
+
+        // Speculative bump of the in-flight counter; the checkpoint restores it
+        // on any abort path so active_transactions never leaks a phantom txn.
+        GLOBAL_IPC_INFO.active_transactions =
+            GLOBAL_IPC_INFO.active_transactions.wrapping_add(1);
+
         let info_ptr = get_ipc_info(txn.sender_uid);
         txn.data_ptr = info_ptr as *mut u8;
         txn.data_size = core::mem::size_of::<IPCInfo>();
-        
+
+        // Charge this transaction's actual processing time to the UID's budget.
+        let throttled = getter.bw_charge(txn.sender_uid, getter.rdtsc().wrapping_sub(start));
+
+        // Abort on a recorded error, a target mismatch, or budget exhaustion;
+        // otherwise commit.
+        let target_ok = getter.get_ipc_targeting_info().target_valid;
+        if txn.return_error != 0 || !(*info_ptr).valid || !target_ok {
+            getter.ipc_rollback(&checkpoint);
+            raise_binder_completion_irq(txn as *mut crate::main::BinderTransaction);
+            return if txn.return_error != 0 { txn.return_error } else { -99 };
+        }
+        if throttled {
+            getter.ipc_rollback(&checkpoint);
+            raise_binder_completion_irq(txn as *mut crate::main::BinderTransaction);
+            return -11;  // EAGAIN: quota exhausted mid-transaction
+        }
+
+        // Transaction done: drop it from the in-flight count before committing,
+        // so active_transactions tracks concurrency rather than growing forever.
+        GLOBAL_IPC_INFO.active_transactions =
+            GLOBAL_IPC_INFO.active_transactions.wrapping_sub(1);
+        getter.ipc_commit();
+        raise_binder_completion_irq(txn as *mut crate::main::BinderTransaction);
         0  // Success
     }
 }
@@ -266,6 +383,10 @@ pub struct IPCStats {
     pub avg_latency_ns: AtomicU32,
     pub uptime_seconds: AtomicU32,
     pub errors_total: AtomicU32,
+    pub bw_quota_us: AtomicU32,
+    pub bw_period_us: AtomicU32,
+    pub bw_throttled_uids: AtomicU32,
+    pub aborts_rolled_back: AtomicU32,
 }
 
 pub static mut IPC_MONITOR: IPCStats = IPCStats {
@@ -279,6 +400,10 @@ pub static mut IPC_MONITOR: IPCStats = IPCStats {
     avg_latency_ns: AtomicU32::new(0),
     uptime_seconds: AtomicU32::new(0),
     errors_total: AtomicU32::new(0),
+    bw_quota_us: AtomicU32::new(IPC_BW_DEFAULT_QUOTA_US as u32),
+    bw_period_us: AtomicU32::new(IPC_BW_DEFAULT_PERIOD_US as u32),
+    bw_throttled_uids: AtomicU32::new(0),
+    aborts_rolled_back: AtomicU32::new(0),
 };
 
 static mut BOOT_TIMESTAMP: u64 = 0;
@@ -294,7 +419,10 @@ impl GetIPCInfo {
             IPC_MONITOR.pages_mapped_total.store(0, Ordering::Relaxed);
             IPC_MONITOR.lock_contention.store(0, Ordering::Relaxed);
             IPC_MONITOR.errors_total.store(0, Ordering::Relaxed);
-            
+
+            // Parse the device-property blob so targeting/paging run off data.
+            crate::devprops::init_device_properties();
+
             // Register monitoring callback with binder driver
             self.register_monitor_callback();
             
@@ -317,15 +445,21 @@ impl GetIPCInfo {
     }
     
     fn register_monitor_callback(&self) {
-        unsafe {
-            // Hook into binder transaction completion
-            core::ptr::write_volatile(
-                0x8000_4000 as *mut unsafe extern "C" fn(*mut crate::main::BinderTransaction),
-                Some(monitor_transaction_hook)
-            );
-        }
+        // Wire the monitoring hook through the GIC vector table rather than a
+        // bare write to 0x8000_4000: the handler occupies the vector slot of the
+        // binder transaction-completion line. That slot holds a single handler,
+        // so a later registration on the same line replaces this one.
+        let gic = crate::gic::Gic::new();
+        gic.init();
+        crate::gic::register_handler(
+            crate::gic::IRQ_BINDER_TXN_COMPLETE,
+            monitor_transaction_hook,
+        );
+        gic.set_priority(crate::gic::IRQ_BINDER_TXN_COMPLETE, 0xA0);
+        gic.set_target_cpu(crate::gic::IRQ_BINDER_TXN_COMPLETE, 0x1);
+        gic.enable_irq(crate::gic::IRQ_BINDER_TXN_COMPLETE);
     }
-    
+
     /// Get comprehensive real-time IPC statistics
     pub fn get_ipc_stats(&self) -> IPCStats {
         unsafe { IPC_MONITOR }
@@ -354,9 +488,19 @@ impl GetIPCInfo {
                 stats.target_mismatches.load(Ordering::Relaxed)
             ));
             crate::main::lumen_os_println(&alloc::format!(
-                "Lock contention: {}", 
+                "Lock contention: {}",
                 stats.lock_contention.load(Ordering::Relaxed)
             ));
+            crate::main::lumen_os_println(&alloc::format!(
+                "Bandwidth: quota {}us / period {}us | throttled UIDs: {}",
+                stats.bw_quota_us.load(Ordering::Relaxed),
+                stats.bw_period_us.load(Ordering::Relaxed),
+                stats.bw_throttled_uids.load(Ordering::Relaxed)
+            ));
+            crate::main::lumen_os_println(&alloc::format!(
+                "Aborted + rolled back: {}",
+                stats.aborts_rolled_back.load(Ordering::Relaxed)
+            ));
         }
     }
 }
@@ -364,10 +508,22 @@ impl GetIPCInfo {
 /// Monitoring hook for every binder transaction
 #[no_mangle]
 pub unsafe extern "C" fn monitor_transaction_hook(txn: *mut crate::main::BinderTransaction) {
+    if txn.is_null() {
+        return; // no transaction published for this IRQ
+    }
     let txn = &*txn;
-    
+
     // Update transaction count
     IPC_MONITOR.transactions_total.fetch_add(1, Ordering::Relaxed);
+
+    // Read pages from the committed snapshot, not the speculative in-flight
+    // state, so an IRQ firing mid-transaction observes a stable view.
+    let getter = GetIPCInfo::new();
+    if let Some(snapshot) = getter.committed_snapshot() {
+        IPC_MONITOR
+            .pages_mapped_total
+            .store(snapshot.pages_mapped, Ordering::Relaxed);
+    }
     
     if txn.data_size > IPC_MONITOR.max_transaction_size.load(Ordering::Relaxed) as usize {
         IPC_MONITOR.max_transaction_size.store(txn.data_size as u32, Ordering::Relaxed);
@@ -390,11 +546,548 @@ pub extern "C" fn sample_ipc_stats() {
     unsafe {
         IPC_MONITOR.uptime_seconds.fetch_add(1, Ordering::Relaxed);
     }
+    // Refill bandwidth groups that have crossed a period boundary.
+    GetIPCInfo::new().bw_refill();
 }
 
 /// C-callable stats dump
 #[no_mangle]
 pub extern "C" fn dump_ipc_monitoring() {
     GetIPCInfo::new().dump_monitoring_report();
-              }
-          
+}
+
+// ======================== MULTI-FORMAT IPC EVENT REGISTRY ========================
+
+// A single frozen C layout keyed only by magic/version means any client that
+// changes its payload breaks every consumer of get_ipc_info, and a hostile UID
+// can clobber a layout others depend on. Instead we key each registered info
+// schema on the pair (event name, format-hash) so two callers may register the
+// same logical name ("ipc_info") with different payload layouts that live in
+// separate namespaces and coexist across an upgrade.
+
+/// Maximum number of simultaneously registered IPC info formats.
+pub const IPC_EVENT_MAX_FORMATS: usize = 16;
+
+/// Maximum length, in bytes, of a logical event name.
+pub const IPC_EVENT_NAME_LEN: usize = 32;
+
+/// Maximum number of fields described per registered layout.
+pub const IPC_EVENT_MAX_FIELDS: usize = 8;
+
+/// Classification of a single field inside a registered payload layout.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum IPCFieldKind {
+    Scalar = 0,
+    ByteArray = 1,
+    UidList = 2,
+}
+
+/// Describes one field of a registered payload: where it lives and how wide.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct IPCFieldDescriptor {
+    pub offset: u16,
+    pub size: u16,
+    pub kind: IPCFieldKind,
+}
+
+/// One registered event format, uniquely identified by (name, format_hash).
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct IPCEventFormat {
+    pub name: [u8; IPC_EVENT_NAME_LEN],
+    pub format_hash: u32,
+    pub size: usize,
+    pub fields: [IPCFieldDescriptor; IPC_EVENT_MAX_FIELDS],
+    pub field_count: u8,
+    pub owner_uid: u32,
+    pub in_use: bool,
+}
+
+impl IPCEventFormat {
+    const fn empty() -> Self {
+        Self {
+            name: [0u8; IPC_EVENT_NAME_LEN],
+            format_hash: 0,
+            size: 0,
+            fields: [IPCFieldDescriptor {
+                offset: 0,
+                size: 0,
+                kind: IPCFieldKind::Scalar,
+            }; IPC_EVENT_MAX_FIELDS],
+            field_count: 0,
+            owner_uid: 0,
+            in_use: false,
+        }
+    }
+
+    fn name_matches(&self, name: &[u8]) -> bool {
+        let n = name.len().min(IPC_EVENT_NAME_LEN);
+        self.name[..n] == name[..n]
+            && (n == IPC_EVENT_NAME_LEN || self.name[n] == 0)
+    }
+}
+
+/// Registry of all known info schemas. Two entries may share a name as long as
+/// their format_hash differs, so layouts never collide across callers.
+pub static mut IPC_EVENT_REGISTRY: [IPCEventFormat; IPC_EVENT_MAX_FORMATS] =
+    [IPCEventFormat::empty(); IPC_EVENT_MAX_FORMATS];
+
+/// FNV-1a over a layout's fields; the canonical format-hash for a schema so
+/// callers that build the same layout independently agree on its identity.
+fn ipc_format_hash(fields: &[IPCFieldDescriptor]) -> u32 {
+    let mut hash: u32 = 0x811C_9DC5;
+    for f in fields {
+        for byte in [
+            (f.offset & 0xFF) as u8,
+            (f.offset >> 8) as u8,
+            (f.size & 0xFF) as u8,
+            (f.size >> 8) as u8,
+            f.kind as u8,
+        ] {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+    }
+    hash
+}
+
+impl GetIPCInfo {
+    /// Register an info schema under `name`. The format-hash is derived from the
+    /// field layout, so re-registering an identical layout is idempotent and a
+    /// differing layout simply occupies its own (name, hash) slot rather than
+    /// overwriting another client's registration. Returns the format-hash on
+    /// success, or `-EINVAL` (-22) when the registry is full.
+    pub fn register_ipc_format(
+        &self,
+        uid: u32,
+        name: &[u8],
+        fields: &[IPCFieldDescriptor],
+        size: usize,
+    ) -> i32 {
+        let hash = ipc_format_hash(fields);
+        unsafe {
+            // Idempotent re-registration of an existing (name, hash) pair.
+            for entry in IPC_EVENT_REGISTRY.iter() {
+                if entry.in_use && entry.format_hash == hash && entry.name_matches(name) {
+                    return hash as i32;
+                }
+            }
+            for entry in IPC_EVENT_REGISTRY.iter_mut() {
+                if !entry.in_use {
+                    let n = name.len().min(IPC_EVENT_NAME_LEN);
+                    entry.name = [0u8; IPC_EVENT_NAME_LEN];
+                    entry.name[..n].copy_from_slice(&name[..n]);
+                    entry.format_hash = hash;
+                    entry.size = size;
+                    let fc = fields.len().min(IPC_EVENT_MAX_FIELDS);
+                    entry.fields[..fc].copy_from_slice(&fields[..fc]);
+                    entry.field_count = fc as u8;
+                    entry.owner_uid = uid;
+                    entry.in_use = true;
+                    return hash as i32;
+                }
+            }
+        }
+        -22 // EINVAL: registry full
+    }
+
+    /// Select a registered schema by its (name, requested_format_hash) pair.
+    pub fn lookup_ipc_format(
+        &self,
+        name: &[u8],
+        requested_format_hash: u32,
+    ) -> Option<&'static IPCEventFormat> {
+        unsafe {
+            IPC_EVENT_REGISTRY.iter().find(|e| {
+                e.in_use && e.format_hash == requested_format_hash && e.name_matches(name)
+            })
+        }
+    }
+}
+
+/// C-callable registration of the default "ipc_info" layout, matching the
+/// frozen `IPCInfo` C struct. New clients register their own variant the same
+/// way; the two coexist keyed on their differing format-hash.
+#[no_mangle]
+pub extern "C" fn register_default_ipc_format() -> i32 {
+    // Offsets mirror the real #[repr(C)] IPCInfo on this 32-bit ARMv7 target
+    // (usize == 4 bytes), so serialize_format reproduces each field's bytes.
+    let fields = [
+        IPCFieldDescriptor { offset: 0, size: 4, kind: IPCFieldKind::Scalar },      // magic
+        IPCFieldDescriptor { offset: 4, size: 4, kind: IPCFieldKind::Scalar },      // version
+        IPCFieldDescriptor { offset: 8, size: 4, kind: IPCFieldKind::Scalar },      // mem_page_size
+        IPCFieldDescriptor { offset: 12, size: 4, kind: IPCFieldKind::Scalar },     // uid_enforced
+        IPCFieldDescriptor { offset: 16, size: 64, kind: IPCFieldKind::ByteArray }, // target_device
+        IPCFieldDescriptor { offset: 80, size: 4, kind: IPCFieldKind::Scalar },     // pages_mapped
+        IPCFieldDescriptor { offset: 84, size: 4, kind: IPCFieldKind::Scalar },     // active_transactions
+        IPCFieldDescriptor { offset: 88, size: 16, kind: IPCFieldKind::ByteArray }, // lock_type
+    ];
+    GetIPCInfo::new().register_ipc_format(
+        SYSTEM_UID,
+        b"ipc_info",
+        &fields,
+        core::mem::size_of::<IPCInfo>(),
+    )
+}
+
+// ======================== CHECKPOINT / ROLLBACK ========================
+
+// Binder IPC transactions mutate shared state (GLOBAL_IPC_INFO, monitor
+// counters, mapped pages) in place, so a mid-flight abort leaves pages_mapped /
+// active_transactions half-updated. Borrowing speculative-then-committed
+// transactional state: checkpoint the affected fields before touching them,
+// roll back on abort, and only publish the speculative updates on success.
+// A monitoring IRQ that fires mid-transaction reads IPC_COMMITTED (the last
+// committed snapshot) via IPC_SNAPSHOT, never the speculative in-flight values.
+
+/// Snapshot of the shared fields a single transaction may mutate.
+/// `transactions_total` is deliberately NOT captured: the monitoring hook bumps
+/// it after the rollback window (via the completion IRQ), so rolling it back
+/// here would be a no-op — the total counts every transaction that reaches the
+/// completion IRQ, committed or rolled back. Transactions rejected at the UID or
+/// throttle gate return before the IRQ and are not counted.
+#[derive(Clone, Copy)]
+pub struct IPCCheckpoint {
+    pub pages_mapped: u32,
+    pub active_transactions: u32,
+    pub valid: bool,
+    pub pages_mapped_total: u32,
+}
+
+/// Double-buffered committed views. A commit writes the *inactive* buffer and
+/// only then publishes its address, so a reentrant reader never observes a
+/// buffer mid-write. The buffer currently pointed at by IPC_SNAPSHOT is the
+/// active (committed) one; the other is free to be overwritten.
+pub static mut IPC_COMMITTED: [IPCInfo; 2] = [
+    IPCInfo {
+        magic: LUMEN_MAGIC,
+        version: 0x10001,
+        mem_page_size: MEM_PAGE_SIZE,
+        uid_enforced: SYSTEM_UID,
+        target_device: [0u8; 64],
+        pages_mapped: 0,
+        active_transactions: 0,
+        lock_type: [0u8; 16],
+        binder_handle: 0xDEADBEEF,
+        server_port: 0x4C495043,
+        valid: false,
+    },
+    IPCInfo {
+        magic: LUMEN_MAGIC,
+        version: 0x10001,
+        mem_page_size: MEM_PAGE_SIZE,
+        uid_enforced: SYSTEM_UID,
+        target_device: [0u8; 64],
+        pages_mapped: 0,
+        active_transactions: 0,
+        lock_type: [0u8; 16],
+        binder_handle: 0xDEADBEEF,
+        server_port: 0x4C495043,
+        valid: false,
+    },
+];
+
+/// Stable snapshot pointer, swapped atomically only at commit. A monitoring IRQ
+/// dereferences this to observe committed — never speculative — state.
+pub static IPC_SNAPSHOT: AtomicPtr<IPCInfo> = AtomicPtr::new(ptr::null_mut());
+
+impl GetIPCInfo {
+    /// Capture the shared fields before a transaction speculatively mutates them.
+    pub fn ipc_checkpoint(&self) -> IPCCheckpoint {
+        unsafe {
+            IPCCheckpoint {
+                pages_mapped: GLOBAL_IPC_INFO.pages_mapped,
+                active_transactions: GLOBAL_IPC_INFO.active_transactions,
+                valid: GLOBAL_IPC_INFO.valid,
+                pages_mapped_total: IPC_MONITOR.pages_mapped_total.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Restore the checkpointed fields after an aborted transaction and record
+    /// the rolled-back abort.
+    pub fn ipc_rollback(&self, cp: &IPCCheckpoint) {
+        unsafe {
+            GLOBAL_IPC_INFO.pages_mapped = cp.pages_mapped;
+            GLOBAL_IPC_INFO.active_transactions = cp.active_transactions;
+            GLOBAL_IPC_INFO.valid = cp.valid;
+            IPC_MONITOR
+                .pages_mapped_total
+                .store(cp.pages_mapped_total, Ordering::Relaxed);
+            IPC_MONITOR.aborts_rolled_back.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Publish the speculative state as committed. Writes GLOBAL_IPC_INFO into
+    /// the *inactive* buffer, then swaps the stable pointer to it with release
+    /// ordering, so a reentrant reader sees either the old or the new view in
+    /// full — never a torn struct from an in-place copy.
+    pub fn ipc_commit(&self) {
+        unsafe {
+            let active = IPC_SNAPSHOT.load(Ordering::Acquire);
+            // Pick the buffer that is NOT currently published.
+            let inactive_idx = if active == &mut IPC_COMMITTED[0] as *mut IPCInfo {
+                1
+            } else {
+                0
+            };
+            IPC_COMMITTED[inactive_idx] = GLOBAL_IPC_INFO;
+            IPC_SNAPSHOT.store(&mut IPC_COMMITTED[inactive_idx] as *mut IPCInfo, Ordering::Release);
+        }
+    }
+
+    /// The last committed snapshot, or `None` before the first commit.
+    pub fn committed_snapshot(&self) -> Option<&'static IPCInfo> {
+        let ptr = IPC_SNAPSHOT.load(Ordering::Acquire);
+        if ptr.is_null() {
+            None
+        } else {
+            unsafe { Some(&*ptr) }
+        }
+    }
+}
+
+/// Capacity of the per-format serialization scratch buffer. This is the backing
+/// buffer a format-aware reply points at, so no advertised `data_size` may
+/// exceed it.
+pub const IPC_FMT_SCRATCH_CAP: usize = 256;
+
+/// Scratch buffer the resolved descriptor serializes into before the reply is
+/// handed back to the caller.
+static mut IPC_FMT_SCRATCH: [u8; IPC_FMT_SCRATCH_CAP] = [0u8; IPC_FMT_SCRATCH_CAP];
+
+impl GetIPCInfo {
+    /// Serialize the live IPCInfo into `IPC_FMT_SCRATCH` laid out per `fmt`'s
+    /// field descriptors, returning the number of bytes produced. Each field is
+    /// copied from the source at its own declared `offset` (not a packed cursor)
+    /// into the same offset in the scratch buffer, so a descriptor must mirror
+    /// the real struct offsets to reproduce its bytes faithfully. Source reads
+    /// past `src_len` and destination writes past the scratch capacity are
+    /// dropped, so the result never exceeds the backing buffer.
+    unsafe fn serialize_format(
+        &self,
+        fmt: &IPCEventFormat,
+        src: *const u8,
+        src_len: usize,
+    ) -> usize {
+        let cap = IPC_FMT_SCRATCH.len();
+        let declared = fmt.size.min(cap);
+        for b in IPC_FMT_SCRATCH[..declared].iter_mut() {
+            *b = 0;
+        }
+        let mut produced = 0usize;
+        for f in fmt.fields[..fmt.field_count as usize].iter() {
+            let off = f.offset as usize;
+            let sz = f.size as usize;
+            if off + sz > cap {
+                continue; // field doesn't fit the backing buffer; skip it
+            }
+            for i in 0..sz {
+                let idx = off + i;
+                IPC_FMT_SCRATCH[idx] = if idx < src_len {
+                    ptr::read_volatile(src.add(idx))
+                } else {
+                    0
+                };
+            }
+            produced = produced.max(off + sz);
+        }
+        produced.min(declared)
+    }
+}
+
+/// Format-aware binder entry point. Resolves the caller's requested schema by
+/// (name, requested_format_hash), serializes the live IPCInfo into that exact
+/// layout, and hands back a reply whose `data_size` matches the bytes actually
+/// produced — so distinct layouts return distinct payloads and the advertised
+/// size never exceeds the backing buffer. Returns `-EINVAL` (-22) only on a
+/// true (name, hash) mismatch, `-EACCES` (-13) on a UID violation, and 0 on
+/// success.
+#[no_mangle]
+pub extern "C" fn binder_get_ipc_info_fmt_handler(
+    txn: *mut crate::main::BinderTransaction,
+    name: *const u8,
+    name_len: usize,
+    requested_format_hash: u32,
+) -> i32 {
+    unsafe {
+        let txn = &mut *txn;
+        let getter = GetIPCInfo::new();
+        // Route the access gate through the property-backed allowed-UID table.
+        if !getter.validate_uid(txn.sender_uid) {
+            return -13; // EACCES
+        }
+
+        let name = slice::from_raw_parts(name, name_len.min(IPC_EVENT_NAME_LEN));
+        match getter.lookup_ipc_format(name, requested_format_hash) {
+            Some(fmt) => {
+                let info_ptr = get_ipc_info(txn.sender_uid);
+                let len = getter.serialize_format(
+                    fmt,
+                    info_ptr as *const u8,
+                    core::mem::size_of::<IPCInfo>(),
+                );
+                txn.data_ptr = IPC_FMT_SCRATCH.as_mut_ptr();
+                txn.data_size = len;
+                0 // Success
+            }
+            None => -22, // EINVAL: no schema registered for (name, hash)
+        }
+    }
+}
+
+// ======================== PER-UID BANDWIDTH CONTROL ========================
+
+// monitor_transaction_hook counts transactions per UID but never bounds them,
+// so one UID can saturate the binder path. This is a CFS-style bandwidth
+// controller: each UID group gets a `quota` (microseconds of transaction-
+// processing time) refilled every `period` (microseconds). Each transaction
+// charges its measured cost against the group's remaining runtime; a group that
+// runs its budget to zero inside a period is throttled until the next period
+// edge, at which sample_ipc_stats refills it.
+
+/// Maximum number of UID bandwidth groups tracked concurrently.
+pub const IPC_BW_MAX_GROUPS: usize = 16;
+
+/// Default quota: 10ms of transaction-processing time per period.
+pub const IPC_BW_DEFAULT_QUOTA_US: u64 = 10_000;
+
+/// Default bandwidth accounting period: 100ms.
+pub const IPC_BW_DEFAULT_PERIOD_US: u64 = 100_000;
+
+/// Cycles-per-microsecond of the ARMv7 virtual counter (Nexus 6 @ 19.2MHz).
+pub const IPC_TIMER_MHZ: u64 = 19;
+
+/// One UID's bandwidth group: a quota/period pair plus live runtime state.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct IPCBandwidthGroup {
+    pub uid: u32,
+    pub quota_us: u64,
+    pub period_us: u64,
+    pub runtime_remaining_us: i64,
+    pub period_start: u64,
+    pub throttled: bool,
+    pub in_use: bool,
+}
+
+impl IPCBandwidthGroup {
+    const fn empty() -> Self {
+        Self {
+            uid: 0,
+            quota_us: IPC_BW_DEFAULT_QUOTA_US,
+            period_us: IPC_BW_DEFAULT_PERIOD_US,
+            runtime_remaining_us: IPC_BW_DEFAULT_QUOTA_US as i64,
+            period_start: 0,
+            throttled: false,
+            in_use: false,
+        }
+    }
+}
+
+pub static mut IPC_BW_GROUPS: [IPCBandwidthGroup; IPC_BW_MAX_GROUPS] =
+    [IPCBandwidthGroup::empty(); IPC_BW_MAX_GROUPS];
+
+/// Total unused runtime accumulated across refills. It is a tally only: no
+/// group draws from it (the per-group credit in `bw_refill` does the carrying).
+pub static mut IPC_BW_UNUSED_US: u64 = 0;
+
+impl GetIPCInfo {
+    /// Find the bandwidth group for `uid`, allocating one with default
+    /// quota/period on first contact. Returns `None` only when the table is
+    /// full (in which case the UID is left unthrottled).
+    fn bw_group_for(&self, uid: u32) -> Option<&'static mut IPCBandwidthGroup> {
+        unsafe {
+            if let Some(idx) = IPC_BW_GROUPS
+                .iter()
+                .position(|g| g.in_use && g.uid == uid)
+            {
+                return Some(&mut IPC_BW_GROUPS[idx]);
+            }
+            if let Some(idx) = IPC_BW_GROUPS.iter().position(|g| !g.in_use) {
+                let g = &mut IPC_BW_GROUPS[idx];
+                *g = IPCBandwidthGroup::empty();
+                g.uid = uid;
+                g.period_start = self.rdtsc();
+                g.in_use = true;
+                return Some(g);
+            }
+        }
+        None
+    }
+
+    /// Charge a transaction's actual processing time (`elapsed_cycles`, measured
+    /// start-to-end around the handler) against the UID's remaining runtime,
+    /// returning `true` if the group is now throttled and the transaction must
+    /// be rejected/queued.
+    pub fn bw_charge(&self, uid: u32, elapsed_cycles: u64) -> bool {
+        if let Some(g) = self.bw_group_for(uid) {
+            let elapsed_us = elapsed_cycles / IPC_TIMER_MHZ;
+            g.runtime_remaining_us -= elapsed_us as i64;
+            if g.runtime_remaining_us <= 0 && !g.throttled {
+                g.throttled = true;
+                unsafe {
+                    IPC_MONITOR.bw_throttled_uids.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            g.throttled
+        } else {
+            false
+        }
+    }
+
+    /// Is `uid` currently throttled? Cheap check for the binder handler.
+    pub fn bw_is_throttled(&self, uid: u32) -> bool {
+        unsafe {
+            IPC_BW_GROUPS
+                .iter()
+                .find(|g| g.in_use && g.uid == uid)
+                .map(|g| g.throttled)
+                .unwrap_or(false)
+        }
+    }
+
+    /// Refill every group whose period has elapsed. A group that finished the
+    /// closing period under budget carries its leftover runtime forward as a
+    /// credit (capped at one quota) so a bursty-but-under-budget UID isn't
+    /// penalised relative to one that idled; that leftover is also accumulated
+    /// into the global `IPC_BW_UNUSED_US` pool for visibility. Clearing the
+    /// throttle decrements the current throttled-UID gauge. Driven from the
+    /// periodic sampler.
+    pub fn bw_refill(&self) {
+        let now = self.rdtsc();
+        unsafe {
+            for g in IPC_BW_GROUPS.iter_mut() {
+                if !g.in_use {
+                    continue;
+                }
+                let period_cycles = g.period_us * IPC_TIMER_MHZ;
+                if now.wrapping_sub(g.period_start) >= period_cycles {
+                    let leftover = g.runtime_remaining_us.max(0);
+                    IPC_BW_UNUSED_US = IPC_BW_UNUSED_US.saturating_add(leftover as u64);
+                    // Carry the under-budget remainder forward as credit.
+                    let credit = leftover.min(g.quota_us as i64);
+                    g.runtime_remaining_us = g.quota_us as i64 + credit;
+                    g.period_start = now;
+                    if g.throttled {
+                        g.throttled = false;
+                        IPC_MONITOR.bw_throttled_uids.fetch_sub(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Set the quota/period for a UID, creating its group if needed. Exposed so
+    /// a policy client can retune contention without a recompile.
+    pub fn bw_set_limit(&self, uid: u32, quota_us: u64, period_us: u64) {
+        if let Some(g) = self.bw_group_for(uid) {
+            g.quota_us = quota_us;
+            g.period_us = period_us;
+            g.runtime_remaining_us = quota_us as i64;
+        }
+    }
+}
+
@@ -0,0 +1,95 @@
+// devprops.rs - Named device-property subsystem for Lumen OS
+// In the style of firmware _DSD / devicetree named properties: parses a blob of
+// key -> value entries at boot so targeting and paging info come from data
+// rather than hardcoded main.rs constants. Retargeting to another ARMv7a board
+// is an edit of the property blob rather than of the code that reads it.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Built-in property blob for the Nexus 6 (shamu) reference target. A board
+/// bring-up replaces this with the blob handed over by firmware; parsing it at
+/// boot keeps the old hardcoded defaults as data rather than constants.
+pub const DEFAULT_DEVICE_BLOB: &str = "\
+# Lumen IPC device properties (shamu reference)
+target.codename=Motorola Nexus 6 shamu
+target.allowed_uids=1000,1001
+ipc.page_size=16384
+ipc.region_pages=64
+";
+
+/// A single parsed key -> value property.
+pub struct DeviceProperty {
+    pub key: String,
+    pub value: String,
+}
+
+/// The parsed property table, populated once at boot by `parse_device_properties`.
+pub static mut DEVICE_PROPERTIES: Vec<DeviceProperty> = Vec::new();
+
+/// Allowed-UID list, sized from the `target.allowed_uids` property rather than a
+/// fixed `[u32; 8]`, so a target may permit as many UIDs as its blob declares.
+pub static mut ALLOWED_UIDS: Vec<u32> = Vec::new();
+
+/// Parse a `key=value` blob (one entry per line, `#` comments ignored) into the
+/// property table. Derived lists such as the allowed-UID array are materialised
+/// here so later lookups are cheap.
+pub fn parse_device_properties(blob: &str) {
+    unsafe {
+        DEVICE_PROPERTIES.clear();
+        ALLOWED_UIDS.clear();
+
+        for line in blob.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim().to_string();
+                let value = value.trim().to_string();
+
+                if key == "target.allowed_uids" {
+                    for tok in value.split(',') {
+                        if let Ok(uid) = tok.trim().parse::<u32>() {
+                            ALLOWED_UIDS.push(uid);
+                        }
+                    }
+                }
+
+                DEVICE_PROPERTIES.push(DeviceProperty { key, value });
+            }
+        }
+    }
+}
+
+/// Look up a property by name, returning its raw string value.
+pub fn get_property(name: &str) -> Option<&'static str> {
+    unsafe {
+        DEVICE_PROPERTIES
+            .iter()
+            .find(|p| p.key == name)
+            .map(|p| p.value.as_str())
+    }
+}
+
+/// Look up a property and parse it as `u32`.
+pub fn get_property_u32(name: &str) -> Option<u32> {
+    get_property(name).and_then(|v| v.trim().parse::<u32>().ok())
+}
+
+/// Look up a property and parse it as `usize`.
+pub fn get_property_usize(name: &str) -> Option<usize> {
+    get_property(name).and_then(|v| v.trim().parse::<usize>().ok())
+}
+
+/// The allowed-UID set declared by `target.allowed_uids`.
+pub fn allowed_uids() -> &'static [u32] {
+    unsafe { ALLOWED_UIDS.as_slice() }
+}
+
+/// Parse the built-in device blob at boot. Called from `init_ipc_monitoring`
+/// so the targeting/paging subsystems run off the property table rather than
+/// the hardcoded fallbacks.
+pub fn init_device_properties() {
+    parse_device_properties(DEFAULT_DEVICE_BLOB);
+}
@@ -0,0 +1,368 @@
+// gdbstub.rs - kgdb-style GDB Remote Serial Protocol stub for Lumen OS
+// Nexus 6 (shamu) ARMv7a. Lets a host attach `arm-none-eabi-gdb` over ttyS0 and
+// inspect live IPC state (GLOBAL_IPC_INFO / IPC_MONITOR) without a rebuild:
+// packet framing, g/G register access, m/M memory access, qSupported/? and
+// custom qRcmd monitor commands routed to the IPC dump routines.
+
+use core::ptr;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// UART (ttyS0) MMIO base on shamu.
+const UART0_BASE: usize = 0xF991_E000;
+const UART_DR: usize = 0x00; // data register
+const UART_FR: usize = 0x18; // flag register
+const UART_FR_RXFE: u32 = 1 << 4; // receive FIFO empty
+const UART_FR_TXFF: u32 = 1 << 5; // transmit FIFO full
+
+/// Number of 32-bit slots in the `g`/`G` packet, matching the target
+/// description served via `qXfer:features:read` below: the ARM core feature
+/// (r0-r12, sp, lr, pc, cpsr = 17 words) followed by the VFP/NEON bank
+/// (d0-d31 as 64-bit doubles = 32 * 2 words, plus fpscr = 65 words) = 82.
+/// Because the host reads the register map from TARGET_XML rather than assuming
+/// a built-in layout, a stock `arm-none-eabi-gdb` both attaches and can inspect
+/// FP/NEON state.
+pub const GDB_NUM_REGS: usize = 82;
+
+/// Target description advertised over `qXfer:features:read:target.xml`. Declares
+/// the ARM core plus the VFP/NEON double bank so the host knows the exact
+/// register layout behind the `g`/`G` packet.
+const TARGET_XML: &str = concat!(
+    "<?xml version=\"1.0\"?>",
+    "<!DOCTYPE target SYSTEM \"gdb-target.dtd\">",
+    "<target version=\"1.0\">",
+    "<architecture>arm</architecture>",
+    "<feature name=\"org.gnu.gdb.arm.core\">",
+    "<reg name=\"r0\" bitsize=\"32\"/>",
+    "<reg name=\"r1\" bitsize=\"32\"/>",
+    "<reg name=\"r2\" bitsize=\"32\"/>",
+    "<reg name=\"r3\" bitsize=\"32\"/>",
+    "<reg name=\"r4\" bitsize=\"32\"/>",
+    "<reg name=\"r5\" bitsize=\"32\"/>",
+    "<reg name=\"r6\" bitsize=\"32\"/>",
+    "<reg name=\"r7\" bitsize=\"32\"/>",
+    "<reg name=\"r8\" bitsize=\"32\"/>",
+    "<reg name=\"r9\" bitsize=\"32\"/>",
+    "<reg name=\"r10\" bitsize=\"32\"/>",
+    "<reg name=\"r11\" bitsize=\"32\"/>",
+    "<reg name=\"r12\" bitsize=\"32\"/>",
+    "<reg name=\"sp\" bitsize=\"32\" type=\"data_ptr\"/>",
+    "<reg name=\"lr\" bitsize=\"32\"/>",
+    "<reg name=\"pc\" bitsize=\"32\" type=\"code_ptr\"/>",
+    "<reg name=\"cpsr\" bitsize=\"32\" regnum=\"25\"/>",
+    "</feature>",
+    "<feature name=\"org.gnu.gdb.arm.vfp\">",
+    "<reg name=\"d0\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d1\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d2\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d3\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d4\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d5\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d6\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d7\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d8\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d9\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d10\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d11\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d12\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d13\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d14\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d15\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d16\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d17\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d18\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d19\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d20\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d21\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d22\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d23\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d24\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d25\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d26\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d27\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d28\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d29\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d30\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"d31\" bitsize=\"64\" type=\"ieee_double\"/>",
+    "<reg name=\"fpscr\" bitsize=\"32\"/>",
+    "</feature>",
+    "</target>",
+);
+
+/// Live CPU register file exposed to the host. Flat 32-bit slots in `g`/`G`
+/// packet order: r0-pc, cpsr, then d0-d31 at two slots each, then fpscr. Past
+/// pc the slot index is not the GDB register number (cpsr is regnum 25 at
+/// slot 16); single-register `p`/`P` packets are not handled.
+#[repr(C)]
+pub struct GdbRegisters {
+    pub regs: [u32; GDB_NUM_REGS],
+}
+
+pub static mut GDB_REGS: GdbRegisters = GdbRegisters {
+    regs: [0u32; GDB_NUM_REGS],
+};
+
+#[inline(always)]
+unsafe fn uart_putc(c: u8) {
+    while ptr::read_volatile((UART0_BASE + UART_FR) as *const u32) & UART_FR_TXFF != 0 {}
+    ptr::write_volatile((UART0_BASE + UART_DR) as *mut u32, c as u32);
+}
+
+#[inline(always)]
+unsafe fn uart_getc() -> u8 {
+    while ptr::read_volatile((UART0_BASE + UART_FR) as *const u32) & UART_FR_RXFE != 0 {}
+    (ptr::read_volatile((UART0_BASE + UART_DR) as *const u32) & 0xFF) as u8
+}
+
+fn hex_nibble(n: u8) -> u8 {
+    match n {
+        0..=9 => b'0' + n,
+        _ => b'a' + (n - 10),
+    }
+}
+
+fn from_hex(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => 0,
+    }
+}
+
+/// Append `byte` to `out` as two lowercase hex digits.
+fn push_hex_byte(out: &mut String, byte: u8) {
+    out.push(hex_nibble(byte >> 4) as char);
+    out.push(hex_nibble(byte & 0xF) as char);
+}
+
+/// Append a little-endian u32 as 8 hex digits (GDB target byte order).
+fn push_hex_u32(out: &mut String, val: u32) {
+    for b in val.to_le_bytes() {
+        push_hex_byte(out, b);
+    }
+}
+
+/// Read one `$<payload>#<csum>` packet from ttyS0, validating the checksum and
+/// replying with `+`/`-`. Returns the raw payload bytes.
+fn read_packet() -> Vec<u8> {
+    loop {
+        unsafe {
+            // Skip to packet start.
+            while uart_getc() != b'$' {}
+
+            let mut payload = Vec::new();
+            let mut sum: u8 = 0;
+            loop {
+                let c = uart_getc();
+                if c == b'#' {
+                    break;
+                }
+                sum = sum.wrapping_add(c);
+                payload.push(c);
+            }
+            let hi = from_hex(uart_getc());
+            let lo = from_hex(uart_getc());
+            let want = (hi << 4) | lo;
+
+            if want == sum {
+                uart_putc(b'+');
+                return payload;
+            }
+            uart_putc(b'-'); // NAK, host retransmits
+        }
+    }
+}
+
+/// Frame and send `payload` as `$<payload>#<csum>`, waiting for the host ACK.
+fn write_packet(payload: &str) {
+    let mut sum: u8 = 0;
+    for b in payload.bytes() {
+        sum = sum.wrapping_add(b);
+    }
+    unsafe {
+        loop {
+            uart_putc(b'$');
+            for b in payload.bytes() {
+                uart_putc(b);
+            }
+            uart_putc(b'#');
+            uart_putc(hex_nibble(sum >> 4));
+            uart_putc(hex_nibble(sum & 0xF));
+            if uart_getc() == b'+' {
+                break;
+            }
+        }
+    }
+}
+
+/// Encode the full register file for a `g` reply.
+fn handle_read_registers() -> String {
+    let mut out = String::new();
+    unsafe {
+        for r in GDB_REGS.regs.iter() {
+            push_hex_u32(&mut out, *r);
+        }
+    }
+    out
+}
+
+/// Decode a `G` payload back into the register file.
+fn handle_write_registers(payload: &[u8]) -> String {
+    unsafe {
+        let mut i = 0;
+        let mut reg = 0;
+        while reg < GDB_NUM_REGS && i + 8 <= payload.len() {
+            let mut bytes = [0u8; 4];
+            for (b, byte) in bytes.iter_mut().enumerate() {
+                let hi = from_hex(payload[i + b * 2]);
+                let lo = from_hex(payload[i + b * 2 + 1]);
+                *byte = (hi << 4) | lo;
+            }
+            GDB_REGS.regs[reg] = u32::from_le_bytes(bytes);
+            i += 8;
+            reg += 1;
+        }
+    }
+    String::from("OK")
+}
+
+/// Parse `addr,len` out of an `m`/`M` packet body.
+fn parse_addr_len(body: &[u8]) -> (usize, usize) {
+    let mut addr = 0usize;
+    let mut len = 0usize;
+    let mut it = body.splitn(2, |&c| c == b',');
+    if let Some(a) = it.next() {
+        for &c in a {
+            addr = (addr << 4) | from_hex(c) as usize;
+        }
+    }
+    if let Some(l) = it.next() {
+        for &c in l {
+            len = (len << 4) | from_hex(c) as usize;
+        }
+    }
+    (addr, len)
+}
+
+/// `m addr,len` - read `len` bytes of target memory as hex. This is how the host
+/// reads the live GLOBAL_IPC_INFO / IPC_MONITOR structures by address.
+fn handle_read_memory(body: &[u8]) -> String {
+    let (addr, len) = parse_addr_len(body);
+    let mut out = String::new();
+    unsafe {
+        for i in 0..len {
+            let byte = ptr::read_volatile((addr + i) as *const u8);
+            push_hex_byte(&mut out, byte);
+        }
+    }
+    out
+}
+
+/// `M addr,len:data` - write hex `data` into target memory.
+fn handle_write_memory(body: &[u8]) -> String {
+    let mut parts = body.splitn(2, |&c| c == b':');
+    let header = parts.next().unwrap_or(&[]);
+    let data = parts.next().unwrap_or(&[]);
+    let (addr, len) = parse_addr_len(header);
+    unsafe {
+        for i in 0..len {
+            if i * 2 + 1 >= data.len() {
+                break;
+            }
+            let hi = from_hex(data[i * 2]);
+            let lo = from_hex(data[i * 2 + 1]);
+            ptr::write_volatile((addr + i) as *mut u8, (hi << 4) | lo);
+        }
+    }
+    String::from("OK")
+}
+
+/// `qRcmd,<hex>` monitor command dispatch: route to the IPC dump routines.
+fn handle_monitor(hex_cmd: &[u8]) -> String {
+    let mut cmd = String::new();
+    let mut i = 0;
+    while i + 1 < hex_cmd.len() {
+        let hi = from_hex(hex_cmd[i]);
+        let lo = from_hex(hex_cmd[i + 1]);
+        cmd.push(((hi << 4) | lo) as char);
+        i += 2;
+    }
+
+    match cmd.trim() {
+        "dump_ipc_info" => {
+            crate::getipcinfo::dump_ipc_info();
+            String::from("OK")
+        }
+        "dump_monitoring_report" => {
+            crate::getipcinfo::dump_ipc_monitoring();
+            String::from("OK")
+        }
+        _ => String::new(), // unsupported monitor command
+    }
+}
+
+/// `qXfer:features:read:<annex>:<offset>,<length>` - serve a slice of the target
+/// description so the host learns the core + VFP/NEON register map. Replies with
+/// `l<data>` for the final (or only) chunk and `m<data>` when more remains.
+fn handle_xfer_features(body: &[u8]) -> String {
+    // Split "<annex>:<offset>,<length>".
+    let mut it = body.splitn(2, |&c| c == b':');
+    let annex = it.next().unwrap_or(&[]);
+    let range = it.next().unwrap_or(&[]);
+    if annex != b"target.xml" {
+        return String::from("E00"); // unknown annex
+    }
+    let (offset, length) = parse_addr_len(range);
+
+    let doc = TARGET_XML.as_bytes();
+    if offset >= doc.len() {
+        return String::from("l"); // past end: nothing left
+    }
+    let end = (offset + length).min(doc.len());
+    let mut out = String::new();
+    out.push(if end == doc.len() { 'l' } else { 'm' });
+    for &b in &doc[offset..end] {
+        out.push(b as char);
+    }
+    out
+}
+
+/// Dispatch a single decoded packet to its handler, returning the reply body.
+fn dispatch(payload: &[u8]) -> String {
+    match payload.first() {
+        Some(b'?') => String::from("S05"), // halted, SIGTRAP
+        Some(b'g') => handle_read_registers(),
+        Some(b'G') => handle_write_registers(&payload[1..]),
+        Some(b'm') => handle_read_memory(&payload[1..]),
+        Some(b'M') => handle_write_memory(&payload[1..]),
+        Some(b'q') => {
+            if payload.starts_with(b"qSupported") {
+                String::from("PacketSize=1024;qXfer:features:read+")
+            } else if payload.starts_with(b"qXfer:features:read:") {
+                handle_xfer_features(&payload[b"qXfer:features:read:".len()..])
+            } else if payload.starts_with(b"qRcmd,") {
+                handle_monitor(&payload[6..])
+            } else if payload.starts_with(b"qC") {
+                String::from("QC01")
+            } else if payload.starts_with(b"qAttached") {
+                String::from("1")
+            } else {
+                String::new()
+            }
+        }
+        _ => String::new(), // empty reply = unsupported
+    }
+}
+
+/// Enter the GDB stub service loop: read packets off ttyS0 and reply forever.
+/// Intended to run from a debug thread or a breakpoint trap handler.
+#[no_mangle]
+pub extern "C" fn gdb_stub_loop() -> ! {
+    loop {
+        let payload = read_packet();
+        let reply = dispatch(&payload);
+        write_packet(&reply);
+    }
+}
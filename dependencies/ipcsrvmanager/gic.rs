@@ -0,0 +1,176 @@
+// gic.rs - ARMv7-A Generic Interrupt Controller (GICv1/v2) driver for Lumen OS
+// Nexus 6 (shamu) ARMv7a. Provides a distributor + CPU-interface register map
+// and a small vector table so binder transaction-completion IRQs dispatch to
+// registered handler slots instead of a hardcoded callback pointer.
+
+use core::ptr;
+
+use crate::main::BinderTransaction;
+
+/// GIC distributor MMIO base (shamu peripheral map).
+pub const GIC_DIST_BASE: usize = 0xF900_0000;
+/// GIC CPU-interface MMIO base.
+pub const GIC_CPU_BASE: usize = 0xF900_2000;
+
+// Distributor register offsets (GICD_*).
+const GICD_CTLR: usize = 0x000;
+const GICD_ISENABLER: usize = 0x100; // set-enable, 1 bit/irq
+const GICD_ICENABLER: usize = 0x180; // clear-enable, 1 bit/irq
+const GICD_IPRIORITYR: usize = 0x400; // 1 byte/irq
+const GICD_ITARGETSR: usize = 0x800; // 1 byte/irq (CPU target mask)
+
+// CPU-interface register offsets (GICC_*).
+const GICC_CTLR: usize = 0x000;
+const GICC_PMR: usize = 0x004; // priority mask
+const GICC_IAR: usize = 0x00C; // interrupt acknowledge
+const GICC_EOIR: usize = 0x010; // end-of-interrupt
+
+/// Number of interrupt IDs tracked by the vector table (SGIs + PPIs + a
+/// modest SPI window, enough for the binder peripheral IRQs).
+pub const GIC_NUM_IRQS: usize = 256;
+
+/// Binder transaction-completion SPI line on shamu.
+pub const IRQ_BINDER_TXN_COMPLETE: u32 = 42;
+
+/// A registered interrupt handler for a binder transaction.
+pub type BinderIrqHandler = unsafe extern "C" fn(*mut BinderTransaction);
+
+/// Vector table: one handler slot per IRQ id. Each line dispatches to a single
+/// handler; subsystems sharing a line do not each get their own slot.
+static mut GIC_VECTORS: [Option<BinderIrqHandler>; GIC_NUM_IRQS] = [None; GIC_NUM_IRQS];
+
+/// The transaction currently being dispatched, handed to each handler.
+static mut GIC_CURRENT_TXN: *mut BinderTransaction = ptr::null_mut();
+
+#[inline(always)]
+unsafe fn mmio_write(base: usize, off: usize, val: u32) {
+    ptr::write_volatile((base + off) as *mut u32, val);
+}
+
+#[inline(always)]
+unsafe fn mmio_read(base: usize, off: usize) -> u32 {
+    ptr::read_volatile((base + off) as *const u32)
+}
+
+/// The ARMv7 GIC driver: thin typed wrapper over the distributor and CPU
+/// interface MMIO windows.
+pub struct Gic {
+    dist_base: usize,
+    cpu_base: usize,
+}
+
+impl Gic {
+    pub const fn new() -> Self {
+        Self {
+            dist_base: GIC_DIST_BASE,
+            cpu_base: GIC_CPU_BASE,
+        }
+    }
+
+    /// Enable the distributor and CPU interface and unmask all priorities.
+    pub fn init(&self) {
+        unsafe {
+            mmio_write(self.dist_base, GICD_CTLR, 0x1); // enable group 0
+            mmio_write(self.cpu_base, GICC_PMR, 0xFF); // allow all priorities
+            mmio_write(self.cpu_base, GICC_CTLR, 0x1); // enable signalling
+        }
+    }
+
+    /// Enable forwarding of interrupt `id` from the distributor.
+    pub fn enable_irq(&self, id: u32) {
+        let reg = (id / 32) as usize * 4;
+        let bit = id % 32;
+        unsafe {
+            mmio_write(self.dist_base, GICD_ISENABLER + reg, 1 << bit);
+        }
+    }
+
+    /// Disable forwarding of interrupt `id`.
+    pub fn disable_irq(&self, id: u32) {
+        let reg = (id / 32) as usize * 4;
+        let bit = id % 32;
+        unsafe {
+            mmio_write(self.dist_base, GICD_ICENABLER + reg, 1 << bit);
+        }
+    }
+
+    /// Set the 8-bit priority for interrupt `id` (lower value = higher priority).
+    pub fn set_priority(&self, id: u32, priority: u8) {
+        let off = GICD_IPRIORITYR + id as usize;
+        let word = off & !0x3;
+        let shift = (off & 0x3) * 8;
+        unsafe {
+            let mut val = mmio_read(self.dist_base, word);
+            val &= !(0xFF << shift);
+            val |= (priority as u32) << shift;
+            mmio_write(self.dist_base, word, val);
+        }
+    }
+
+    /// Route interrupt `id` to the CPUs named in `cpu_mask` (bit per core).
+    pub fn set_target_cpu(&self, id: u32, cpu_mask: u8) {
+        let off = GICD_ITARGETSR + id as usize;
+        let word = off & !0x3;
+        let shift = (off & 0x3) * 8;
+        unsafe {
+            let mut val = mmio_read(self.dist_base, word);
+            val &= !(0xFF << shift);
+            val |= (cpu_mask as u32) << shift;
+            mmio_write(self.dist_base, word, val);
+        }
+    }
+
+    /// Acknowledge the pending interrupt, returning its ID (from GICC_IAR).
+    pub fn acknowledge(&self) -> u32 {
+        unsafe { mmio_read(self.cpu_base, GICC_IAR) & 0x3FF }
+    }
+
+    /// Signal end-of-interrupt for `id` (GICC_EOIR).
+    pub fn eoi(&self, id: u32) {
+        unsafe {
+            mmio_write(self.cpu_base, GICC_EOIR, id);
+        }
+    }
+}
+
+/// Register a handler in the vector table slot for `irq_id`, replacing any
+/// handler already registered on that id. Ids outside the table are ignored.
+pub fn register_handler(irq_id: u32, handler: BinderIrqHandler) {
+    if (irq_id as usize) < GIC_NUM_IRQS {
+        unsafe {
+            GIC_VECTORS[irq_id as usize] = Some(handler);
+        }
+    }
+}
+
+/// Stash the in-flight transaction so the IRQ handler can read it when the
+/// binder completion line fires.
+pub fn set_pending_transaction(txn: *mut BinderTransaction) {
+    unsafe {
+        GIC_CURRENT_TXN = txn;
+    }
+}
+
+/// Invoke the registered handler for a known interrupt `id` and signal EOI.
+/// Used for software-driven completions where the line is already known, so we
+/// must not trust `GICC_IAR` (which would return the spurious id 1023 with no
+/// genuine hardware interrupt pending).
+pub fn gic_dispatch_line(id: u32) {
+    let gic = Gic::new();
+    unsafe {
+        if (id as usize) < GIC_NUM_IRQS {
+            if let Some(handler) = GIC_VECTORS[id as usize] {
+                handler(GIC_CURRENT_TXN);
+            }
+        }
+    }
+    gic.eoi(id);
+}
+
+/// Top-level hardware IRQ entry: read the acknowledged line from `GICC_IAR`,
+/// dispatch it, then signal EOI. Wired as the GIC vector for real binder events.
+#[no_mangle]
+pub extern "C" fn gic_dispatch_irq() {
+    let id = Gic::new().acknowledge();
+    gic_dispatch_line(id);
+}